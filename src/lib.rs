@@ -1,23 +1,37 @@
-//! This crate provides utilities for efficiently extracting the inner value from a `Box<T>`
-//! without unnecessarily running the destructor of the contained value.
-//! 
-//! The main functionality is implemented using `std::mem::transmute` and `std::mem::MaybeUninit`
-//! to safely convert a `Box<T>` directly into the inner value `T` without dropping it.
-//! This can be useful in performance-critical code where you want to avoid the overhead
-//! of running destructors unnecessarily.
-//! 
+//! This crate provides utilities for efficiently extracting the inner value from `Box<T>`,
+//! `Rc<T>`, `Arc<T>`, and `Box<[T]>` without unnecessarily running the destructor of the
+//! contained value, via the [`IntoInner`] trait and its free-function equivalents.
+//!
+//! `Box<T>` and (with the `allocator_api` feature) `Box<T, A>` additionally implement
+//! [`TakeInner`], which hands back the emptied allocation alongside the extracted value so
+//! it can be recycled ([`box_take`]/[`box_put`]), dropped-in-place and kept
+//! ([`drop_contents`]), or extracted with the freed heap bytes zeroed
+//! ([`TakeInner::into_inner_zeroed`]) for sensitive data.
+//!
+//! `Rc<T>`/`Arc<T>` may be shared, so [`IntoInner::into_inner`] for those types returns
+//! `Result<T, Self>`: the value is only ever moved out when the calling handle is the sole
+//! strong reference with no outstanding weak references; otherwise the original handle is
+//! handed back unchanged.
+//!
 //! ## Example
-//! 
+//!
 //! ```
 //! use box_into_inner::IntoInner;
-//! 
+//!
 //! let boxed_value = Box::new("Hello, World!");
 //! let inner_value = boxed_value.into_inner();
 //!
 //! assert_eq!(inner_value, "Hello, World!");
 //! ```
 
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
 use std::mem::{self, MaybeUninit};
+use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+#[cfg(feature = "allocator_api")]
+use std::alloc::Allocator;
 
 /// Extracts the inner value from a `Box<T>`.
 /// 
@@ -45,11 +59,403 @@ pub fn box_into_inner<T>(value: Box<T>) -> T {
     unsafe { boxed_uninit.assume_init_read() }
 }
 
+/// Extracts the inner value from a `Box<T, A>` allocated with a custom allocator.
+///
+/// This is the allocator-aware counterpart to [`box_into_inner`]. It decomposes the box
+/// into its raw pointer and allocator, reads the value out without dropping it, then
+/// reconstructs a `Box<MaybeUninit<T>, A>` from the same pointer and allocator so the
+/// original allocation is freed by the correct allocator.
+///
+/// # Example
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use box_into_inner::box_into_inner_in;
+///
+/// let boxed_value = Box::new_in(vec![1, 2, 3, 4, 5], Global);
+/// let inner_value = box_into_inner_in(boxed_value);
+/// assert_eq!(inner_value, vec![1, 2, 3, 4, 5]);
+/// ```
+#[cfg(feature = "allocator_api")]
+#[inline]
+pub fn box_into_inner_in<T, A: Allocator>(value: Box<T, A>) -> T {
+    let (ptr, alloc) = Box::into_raw_with_allocator(value);
+
+    // Safety: `Box::into_raw_with_allocator` hands us sole ownership of `ptr`, which still
+    // points at the live, fully initialized `T` the box was holding.
+    let inner = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` still points to the allocation backing the original box, now
+    // reinterpreted as `MaybeUninit<T>`; dropping this box frees that allocation through
+    // `alloc` without running `T`'s destructor.
+    drop(unsafe { Box::from_raw_in(ptr.cast::<MaybeUninit<T>>(), alloc) });
+
+    inner
+}
+
+/// Extracts the inner value from a `Box<T>`, keeping the allocation alive for reuse.
+///
+/// This is a companion to [`box_into_inner`] for hot loops that want to recycle the box's
+/// heap block instead of freeing and re-allocating it. It returns both the extracted `T`
+/// and the now-empty allocation as a `Box<MaybeUninit<T>>`; pair it with [`box_put`] to
+/// write a new value back into the recycled slot.
+///
+/// # Example
+///
+/// ```
+/// use box_into_inner::box_take;
+///
+/// let boxed_value = Box::new(vec![1, 2, 3, 4, 5]);
+/// let (inner_value, empty_box) = box_take(boxed_value);
+/// assert_eq!(inner_value, vec![1, 2, 3, 4, 5]);
+/// drop(empty_box);
+/// ```
+#[inline]
+pub fn box_take<T>(b: Box<T>) -> (T, Box<MaybeUninit<T>>) {
+    let ptr = Box::into_raw(b);
+
+    // Safety: `Box::into_raw` gives us the one and only pointer to the box's live `T`.
+    let value = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` still points to the allocation backing the original box; reinterpreting
+    // it as `MaybeUninit<T>` hands back that same allocation without dropping `T` again.
+    let empty_box = unsafe { Box::from_raw(ptr.cast::<MaybeUninit<T>>()) };
+
+    (value, empty_box)
+}
+
+/// Allocator-aware counterpart to [`box_take`] for `Box<T, A>`.
+///
+/// # Example
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use box_into_inner::box_take_in;
+///
+/// let boxed_value = Box::new_in(vec![1, 2, 3, 4, 5], Global);
+/// let (inner_value, empty_box) = box_take_in(boxed_value);
+/// assert_eq!(inner_value, vec![1, 2, 3, 4, 5]);
+/// drop(empty_box);
+/// ```
+#[cfg(feature = "allocator_api")]
+#[inline]
+pub fn box_take_in<T, A: Allocator>(b: Box<T, A>) -> (T, Box<MaybeUninit<T>, A>) {
+    let (ptr, alloc) = Box::into_raw_with_allocator(b);
+
+    // Safety: `ptr` is the sole pointer to the box's live `T`, handed to us by
+    // `Box::into_raw_with_allocator` along with the allocator that owns it.
+    let value = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` still points to the allocation backing the original box; reinterpreting
+    // it as `MaybeUninit<T>` hands back that same allocation without dropping `T` again.
+    let empty_box = unsafe { Box::from_raw_in(ptr.cast::<MaybeUninit<T>>(), alloc) };
+
+    (value, empty_box)
+}
+
+/// Writes `value` into a recycled allocation produced by [`box_take`], returning an
+/// initialized `Box<T>` that reuses that allocation instead of allocating a new one.
+///
+/// # Example
+///
+/// ```
+/// use box_into_inner::{box_put, box_take};
+///
+/// let boxed_value = Box::new(vec![1, 2, 3, 4, 5]);
+/// let (inner_value, empty_box) = box_take(boxed_value);
+/// let boxed_again = box_put(empty_box, inner_value);
+/// assert_eq!(*boxed_again, vec![1, 2, 3, 4, 5]);
+/// ```
+#[inline]
+pub fn box_put<T>(mut b: Box<MaybeUninit<T>>, value: T) -> Box<T> {
+    b.write(value);
+
+    // Safety: `value` was just written into `b`, so it is fully initialized.
+    unsafe { b.assume_init() }
+}
+
+/// Runs `T`'s destructor on a `Box<T>` and hands back the now-uninitialized allocation.
+///
+/// This is the opposite of [`box_take`]: it runs the destructor exactly once (via
+/// [`ptr::drop_in_place`]) instead of skipping it, but still keeps the allocation alive for
+/// reuse. Together the two cover both sides of allocation pooling, depending on whether the
+/// destructor needs to run before the slot is refilled.
+///
+/// # Example
+///
+/// ```
+/// use box_into_inner::drop_contents;
+///
+/// let boxed_value = Box::new(vec![1, 2, 3, 4, 5]);
+/// let empty_box = drop_contents(boxed_value);
+/// drop(empty_box);
+/// ```
+#[inline]
+pub fn drop_contents<T>(b: Box<T>) -> Box<MaybeUninit<T>> {
+    let ptr = Box::into_raw(b);
+
+    // Safety: `ptr` is the sole owning pointer to the box's `T`, so this runs its
+    // destructor exactly once, as a normal drop of the box would.
+    unsafe { ptr::drop_in_place(ptr) };
+
+    // Safety: `T` was just dropped in place, so `ptr` now points to uninitialized memory
+    // that is valid to read as `MaybeUninit<T>`; this box frees that allocation on drop.
+    unsafe { Box::from_raw(ptr.cast::<MaybeUninit<T>>()) }
+}
+
+/// Allocator-aware counterpart to [`drop_contents`] for `Box<T, A>`.
+///
+/// # Example
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use box_into_inner::drop_contents_in;
+///
+/// let boxed_value = Box::new_in(vec![1, 2, 3, 4, 5], Global);
+/// let empty_box = drop_contents_in(boxed_value);
+/// drop(empty_box);
+/// ```
+#[cfg(feature = "allocator_api")]
+#[inline]
+pub fn drop_contents_in<T, A: Allocator>(b: Box<T, A>) -> Box<MaybeUninit<T>, A> {
+    let (ptr, alloc) = Box::into_raw_with_allocator(b);
+
+    // Safety: `ptr` is the sole owning pointer to the box's `T`, so this runs its
+    // destructor exactly once, as a normal drop of the box would.
+    unsafe { ptr::drop_in_place(ptr) };
+
+    // Safety: `T` was just dropped in place, so `ptr` now points to uninitialized memory
+    // that is valid to read as `MaybeUninit<T>`; this box frees that allocation through
+    // `alloc` on drop.
+    unsafe { Box::from_raw_in(ptr.cast::<MaybeUninit<T>>(), alloc) }
+}
+
+/// Extracts the inner value from a `Box<T>`, scrubbing the freed heap bytes with zeros so
+/// sensitive data does not linger in reclaimed memory or later swap/crash dumps.
+///
+/// The value is read out exactly as in [`box_into_inner`], then the now-empty allocation is
+/// overwritten byte-by-byte with volatile zero writes (so the optimizer cannot elide them)
+/// before it is deallocated. This only scrubs the heap copy: it does not erase any stack
+/// copies the compiler may make of the returned value.
+///
+/// # Example
+///
+/// ```
+/// use box_into_inner::box_into_inner_zeroed;
+///
+/// let boxed_secret = Box::new([0x42u8; 32]);
+/// let secret = box_into_inner_zeroed(boxed_secret);
+/// assert_eq!(secret, [0x42u8; 32]);
+/// ```
+#[inline]
+pub fn box_into_inner_zeroed<T>(b: Box<T>) -> T {
+    let ptr = Box::into_raw(b);
+
+    // Safety: `ptr` is the sole pointer to the box's live `T`; we read it out before the
+    // bytes underneath are scrubbed below.
+    let value = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` is valid for `size_of::<T>()` bytes and nothing else reads through it
+    // until it is reinterpreted as `MaybeUninit<T>` below; the volatile writes scrub the
+    // heap copy of `value` before the allocation is freed.
+    let bytes = ptr.cast::<u8>();
+    for i in 0..mem::size_of::<T>() {
+        unsafe { bytes.add(i).write_volatile(0) };
+    }
+
+    // Safety: the allocation is now all zero bytes, which is valid for `MaybeUninit<T>`;
+    // dropping this box frees the allocation without running `T`'s destructor again.
+    drop(unsafe { Box::from_raw(ptr.cast::<MaybeUninit<T>>()) });
+
+    value
+}
+
+/// Allocator-aware counterpart to [`box_into_inner_zeroed`] for `Box<T, A>`.
+///
+/// # Example
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use std::alloc::Global;
+/// use box_into_inner::box_into_inner_zeroed_in;
+///
+/// let boxed_secret = Box::new_in([0x42u8; 32], Global);
+/// let secret = box_into_inner_zeroed_in(boxed_secret);
+/// assert_eq!(secret, [0x42u8; 32]);
+/// ```
+#[cfg(feature = "allocator_api")]
+#[inline]
+pub fn box_into_inner_zeroed_in<T, A: Allocator>(b: Box<T, A>) -> T {
+    let (ptr, alloc) = Box::into_raw_with_allocator(b);
+
+    // Safety: `ptr` is the sole pointer to the box's live `T`, handed to us by
+    // `Box::into_raw_with_allocator`; we read it out before scrubbing the bytes beneath.
+    let value = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` is valid for `size_of::<T>()` bytes and nothing else reads through it
+    // until it is reinterpreted as `MaybeUninit<T>` below; the volatile writes scrub the
+    // heap copy of `value` before the allocation is freed.
+    let bytes = ptr.cast::<u8>();
+    for i in 0..mem::size_of::<T>() {
+        unsafe { bytes.add(i).write_volatile(0) };
+    }
+
+    // Safety: the allocation is now all zero bytes, which is valid for `MaybeUninit<T>`;
+    // dropping this box frees the allocation through `alloc` without running `T`'s
+    // destructor again.
+    drop(unsafe { Box::from_raw_in(ptr.cast::<MaybeUninit<T>>(), alloc) });
+
+    value
+}
+
+/// Moves the elements of a boxed slice into a `Vec<T>`, transferring the backing allocation
+/// intact instead of reallocating and moving each element.
+///
+/// This covers the common boxed-slice case that [`box_into_inner`] cannot handle, since that
+/// function only works for `Sized` payloads. The conversion itself is just `std`'s own
+/// `From<Box<[T]>> for Vec<T>`, which already performs (and has already had audited) the
+/// same allocation-transferring trick this crate uses elsewhere.
+///
+/// # Example
+///
+/// ```
+/// use box_into_inner::boxed_slice_into_vec;
+///
+/// let boxed_slice: Box<[i32]> = vec![1, 2, 3, 4, 5].into_boxed_slice();
+/// let vec = boxed_slice_into_vec(boxed_slice);
+/// assert_eq!(vec, vec![1, 2, 3, 4, 5]);
+/// ```
+#[inline]
+pub fn boxed_slice_into_vec<T>(b: Box<[T]>) -> Vec<T> {
+    Vec::from(b)
+}
+
+/// Extracts the inner value from an `Rc<T>` if this is the only strong reference and there
+/// are no weak references, without dropping it.
+///
+/// Unlike `Box<T>`, an `Rc<T>` may be shared, so the value can only be moved out when no
+/// other handle could still observe it. If `this` is the sole owner, the value is read out
+/// of the shared allocation and the allocation is then reinterpreted as `Rc<MaybeUninit<T>>`
+/// so it is freed without running `T`'s destructor a second time. Otherwise `this` is
+/// returned unchanged, since another owner may still be relying on the payload.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Rc;
+/// use box_into_inner::rc_into_inner;
+///
+/// let shared = Rc::new(vec![1, 2, 3, 4, 5]);
+/// let inner_value = rc_into_inner(shared).unwrap();
+/// assert_eq!(inner_value, vec![1, 2, 3, 4, 5]);
+/// ```
+///
+/// Returns the `Rc` back, unchanged and still usable, when another strong or weak
+/// reference is outstanding:
+///
+/// ```
+/// use std::rc::Rc;
+/// use box_into_inner::rc_into_inner;
+///
+/// let shared = Rc::new(vec![1, 2, 3, 4, 5]);
+/// let clone = Rc::clone(&shared);
+/// let shared = rc_into_inner(shared).unwrap_err();
+/// assert_eq!(*shared, vec![1, 2, 3, 4, 5]);
+/// drop(clone);
+///
+/// let weak = Rc::downgrade(&shared);
+/// let shared = rc_into_inner(shared).unwrap_err();
+/// assert!(weak.upgrade().is_some());
+/// ```
+#[inline]
+pub fn rc_into_inner<T>(this: Rc<T>) -> Result<T, Rc<T>> {
+    if Rc::strong_count(&this) != 1 || Rc::weak_count(&this) != 0 {
+        return Err(this);
+    }
+
+    let ptr = Rc::into_raw(this);
+
+    // Safety: `this` was the sole strong reference with no weak references, so `ptr` is a
+    // valid, initialized, uniquely-owned `T`.
+    let value = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` still points to the allocation backing the original `Rc`, now
+    // reinterpreted as `MaybeUninit<T>`; dropping this `Rc` frees that allocation without
+    // running `T`'s destructor.
+    drop(unsafe { Rc::from_raw(ptr.cast::<MaybeUninit<T>>()) });
+
+    Ok(value)
+}
+
+/// Extracts the inner value from an `Arc<T>` if this is the only strong reference and there
+/// are no weak references, without dropping it.
+///
+/// This is the atomically-reference-counted counterpart to [`rc_into_inner`]; see its
+/// documentation for the sharing caveats.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use box_into_inner::arc_into_inner;
+///
+/// let shared = Arc::new(vec![1, 2, 3, 4, 5]);
+/// let inner_value = arc_into_inner(shared).unwrap();
+/// assert_eq!(inner_value, vec![1, 2, 3, 4, 5]);
+/// ```
+///
+/// Returns the `Arc` back, unchanged and still usable, when another strong or weak
+/// reference is outstanding:
+///
+/// ```
+/// use std::sync::Arc;
+/// use box_into_inner::arc_into_inner;
+///
+/// let shared = Arc::new(vec![1, 2, 3, 4, 5]);
+/// let clone = Arc::clone(&shared);
+/// let shared = arc_into_inner(shared).unwrap_err();
+/// assert_eq!(*shared, vec![1, 2, 3, 4, 5]);
+/// drop(clone);
+///
+/// let weak = Arc::downgrade(&shared);
+/// let shared = arc_into_inner(shared).unwrap_err();
+/// assert!(weak.upgrade().is_some());
+/// ```
+#[inline]
+pub fn arc_into_inner<T>(this: Arc<T>) -> Result<T, Arc<T>> {
+    if Arc::strong_count(&this) != 1 || Arc::weak_count(&this) != 0 {
+        return Err(this);
+    }
+
+    // The count checks above only load the counters with `Relaxed` ordering. Mirroring
+    // `Arc`'s own drop glue, an acquire fence is needed here to synchronize with the
+    // `Release` decrement performed by whichever thread most recently dropped a sibling
+    // clone, so that thread's reads/writes of the payload happen-before ours.
+    std::sync::atomic::fence(std::sync::atomic::Ordering::Acquire);
+
+    let ptr = Arc::into_raw(this);
+
+    // Safety: `this` was the sole strong reference with no weak references, and the fence
+    // above orders out any lingering access from a just-dropped sibling, so `ptr` is a
+    // valid, initialized, uniquely-owned `T`.
+    let value = unsafe { ptr::read(ptr) };
+
+    // Safety: `ptr` still points to the allocation backing the original `Arc`, now
+    // reinterpreted as `MaybeUninit<T>`; dropping this `Arc` frees that allocation without
+    // running `T`'s destructor.
+    drop(unsafe { Arc::from_raw(ptr.cast::<MaybeUninit<T>>()) });
+
+    Ok(value)
+}
+
 /// A trait that provides a method to extract the inner value from a container
 /// without running its destructor.
-/// 
-/// Currently implemented for `Box<T>`, allowing you to call `.into_inner()` 
-/// directly on boxed values.
+///
+/// Implemented for `Box<T>`, `Box<T, A>` (with the `allocator_api` feature), `Box<[T]>`,
+/// `Rc<T>`, and `Arc<T>`, allowing you to call `.into_inner()` directly on any of them.
 pub trait IntoInner {
     /// The inner type contained in the container.
     type Inner;
@@ -58,6 +464,29 @@ pub trait IntoInner {
     fn into_inner(self) -> Self::Inner;
 }
 
+/// An extension of [`IntoInner`] for containers that own an allocation unconditionally
+/// (never shared, unlike `Rc`/`Arc`), so extracting the inner value can always hand back
+/// the emptied allocation for reuse.
+pub trait TakeInner: IntoInner {
+    /// What remains of the container after [`take`](TakeInner::take) extracts the inner
+    /// value, typically an empty allocation that can be refilled later.
+    type Empty;
+
+    /// Extracts the inner value, also returning the emptied container so its allocation
+    /// can be reused instead of being freed.
+    fn take(self) -> (Self::Inner, Self::Empty);
+
+    /// Runs the inner value's destructor, returning the now-uninitialized container so its
+    /// allocation can be reused instead of being freed.
+    fn drop_contents(self) -> Self::Empty;
+
+    /// Extracts the inner value, scrubbing the freed allocation with zeros so sensitive
+    /// data does not linger in reclaimed heap memory. This only scrubs the heap copy, not
+    /// any stack copies the compiler may make of the returned value.
+    fn into_inner_zeroed(self) -> Self::Inner;
+}
+
+#[cfg(not(feature = "allocator_api"))]
 impl<T> IntoInner for Box<T> {
     type Inner = T;
 
@@ -66,3 +495,80 @@ impl<T> IntoInner for Box<T> {
         box_into_inner(self)
     }
 }
+
+#[cfg(not(feature = "allocator_api"))]
+impl<T> TakeInner for Box<T> {
+    type Empty = Box<MaybeUninit<T>>;
+
+    #[inline]
+    fn take(self) -> (Self::Inner, Self::Empty) {
+        box_take(self)
+    }
+
+    #[inline]
+    fn drop_contents(self) -> Self::Empty {
+        drop_contents(self)
+    }
+
+    #[inline]
+    fn into_inner_zeroed(self) -> Self::Inner {
+        box_into_inner_zeroed(self)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> IntoInner for Box<T, A> {
+    type Inner = T;
+
+    #[inline]
+    fn into_inner(self) -> Self::Inner {
+        box_into_inner_in(self)
+    }
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> TakeInner for Box<T, A> {
+    type Empty = Box<MaybeUninit<T>, A>;
+
+    #[inline]
+    fn take(self) -> (Self::Inner, Self::Empty) {
+        box_take_in(self)
+    }
+
+    #[inline]
+    fn drop_contents(self) -> Self::Empty {
+        drop_contents_in(self)
+    }
+
+    #[inline]
+    fn into_inner_zeroed(self) -> Self::Inner {
+        box_into_inner_zeroed_in(self)
+    }
+}
+
+impl<T> IntoInner for Box<[T]> {
+    type Inner = Vec<T>;
+
+    #[inline]
+    fn into_inner(self) -> Self::Inner {
+        boxed_slice_into_vec(self)
+    }
+}
+
+impl<T> IntoInner for Rc<T> {
+    type Inner = Result<T, Self>;
+
+    #[inline]
+    fn into_inner(self) -> Self::Inner {
+        rc_into_inner(self)
+    }
+}
+
+impl<T> IntoInner for Arc<T> {
+    type Inner = Result<T, Self>;
+
+    #[inline]
+    fn into_inner(self) -> Self::Inner {
+        arc_into_inner(self)
+    }
+}